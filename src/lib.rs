@@ -40,7 +40,7 @@
 //! See [PinTree](struct.PinTree.html)
 
 use std::collections::hash_set::Iter;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, TryReserveError, VecDeque};
 use std::convert::AsRef;
 use std::fmt;
 use std::fmt::{Display, Formatter};
@@ -49,6 +49,13 @@ use std::ops::Deref;
 use std::pin::Pin;
 use std::sync::Arc;
 
+use sha2::{Digest, Sha256};
+
+/// Fixed digest used for a node with no children
+const EMPTY_CHILDREN_HASH: [u8; 32] = [0u8; 32];
+/// Fixed digest used in place of recursing into an already-visited node (a cycle back-edge)
+const PRUNED_HASH: [u8; 32] = [0xffu8; 32];
+
 trait HashBox {
     fn get_ptr_usize(&self) -> usize;
 }
@@ -100,6 +107,16 @@ impl<T> PinTree<T> {
             false
         }
     }
+    /// The childs of `node`, in a deterministic (pointer-sorted) order
+    fn sorted_childs(&self, node: &PinNode<T>) -> Vec<PinNode<T>> {
+        let mut childs: Vec<PinNode<T>> = self
+            .childs
+            .get(node)
+            .map(|childs| childs.iter().cloned().collect())
+            .unwrap_or_default();
+        childs.sort_by_key(|c| c.ptr_usize());
+        childs
+    }
     /// Set parent-child relationship   
     /// If nodes are not in PinTree, they will be added
     pub fn set_parent(&mut self, this: &PinNode<T>, parent: &PinNode<T>) -> bool {
@@ -108,6 +125,9 @@ impl<T> PinTree<T> {
         if self.is_parent(this, parent) {
             return false;
         }
+        if let Some(old_parent) = self.parents.get(this).cloned() {
+            self.remove_child(&old_parent, this);
+        }
         self.remove_child(parent, this);
         self.parents.insert(this.clone(), parent.clone());
         if !self.childs.contains_key(parent) {
@@ -151,6 +171,256 @@ impl<T> PinTree<T> {
             .map(|childs| childs.iter())
             .unwrap_or(self._empty_node_set.iter())
     }
+    /// Depth-first pre-order traversal of `node` and its descendants (cycle-safe)
+    pub fn descendants_pre_order(&self, node: &PinNode<T>) -> NodeIter<T> {
+        let mut visited = HashSet::new();
+        let mut out = Vec::new();
+        let mut stack = vec![node.clone()];
+        while let Some(n) = stack.pop() {
+            if !visited.insert(n.clone()) {
+                continue;
+            }
+            if let Some(childs) = self.childs.get(&n) {
+                for c in childs.iter() {
+                    stack.push(c.clone());
+                }
+            }
+            out.push(n);
+        }
+        NodeIter { inner: out.into_iter() }
+    }
+    /// Depth-first post-order traversal of `node` and its descendants (cycle-safe)
+    pub fn descendants_post_order(&self, node: &PinNode<T>) -> NodeIter<T> {
+        let mut visited = HashSet::new();
+        let mut out = Vec::new();
+        self.post_order_walk(node, &mut visited, &mut out);
+        NodeIter { inner: out.into_iter() }
+    }
+    fn post_order_walk(
+        &self,
+        node: &PinNode<T>,
+        visited: &mut HashSet<PinNode<T>>,
+        out: &mut Vec<PinNode<T>>,
+    ) {
+        if !visited.insert(node.clone()) {
+            return;
+        }
+        if let Some(childs) = self.childs.get(node) {
+            for c in childs.iter() {
+                self.post_order_walk(c, visited, out);
+            }
+        }
+        out.push(node.clone());
+    }
+    /// Breadth-first (level-order) traversal of `node` and its descendants (cycle-safe)
+    pub fn descendants_level_order(&self, node: &PinNode<T>) -> NodeIter<T> {
+        let mut visited = HashSet::new();
+        let mut out = Vec::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(node.clone());
+        while let Some(n) = queue.pop_front() {
+            if !visited.insert(n.clone()) {
+                continue;
+            }
+            if let Some(childs) = self.childs.get(&n) {
+                for c in childs.iter() {
+                    queue.push_back(c.clone());
+                }
+            }
+            out.push(n);
+        }
+        NodeIter { inner: out.into_iter() }
+    }
+    /// Walk `parents` upward from `node` to the root, not including `node` itself
+    /// Safe against cycles: stops as soon as a node would be visited twice
+    /// See [`path_to_root`](Self::path_to_root) for a version that includes `node`
+    pub fn ancestors(&self, node: &PinNode<T>) -> NodeIter<T> {
+        let mut visited = HashSet::new();
+        let mut out = Vec::new();
+        visited.insert(node.clone());
+        let mut current = self.parents.get(node).cloned();
+        while let Some(n) = current {
+            if !visited.insert(n.clone()) {
+                break;
+            }
+            current = self.parents.get(&n).cloned();
+            out.push(n);
+        }
+        NodeIter { inner: out.into_iter() }
+    }
+    /// Greedy heaviest-subtree descent from `start`, inspired by the LMD-GHOST fork choice rule (cycle-safe)
+    pub fn find_head<F: Fn(&PinNode<T>) -> u64>(&self, start: &PinNode<T>, weight: F) -> PinNode<T> {
+        let mut visited = HashSet::new();
+        let mut weights = HashMap::new();
+        self.subtree_weight(start, &weight, &mut visited, &mut weights);
+
+        let mut current = start.clone();
+        let mut descended = HashSet::new();
+        descended.insert(current.clone());
+        loop {
+            let childs = match self.childs.get(&current) {
+                Some(childs) if !childs.is_empty() => childs,
+                _ => return current,
+            };
+            let mut best: Option<&PinNode<T>> = None;
+            for child in childs.iter() {
+                if descended.contains(child) {
+                    continue;
+                }
+                let child_weight = *weights.get(child).unwrap_or(&0);
+                best = Some(match best {
+                    None => child,
+                    Some(b) => {
+                        let best_weight = *weights.get(b).unwrap_or(&0);
+                        if child_weight > best_weight
+                            || (child_weight == best_weight && child.ptr_usize() > b.ptr_usize())
+                        {
+                            child
+                        } else {
+                            b
+                        }
+                    }
+                });
+            }
+            match best {
+                Some(b) => current = b.clone(),
+                None => return current,
+            }
+            descended.insert(current.clone());
+        }
+    }
+    fn subtree_weight<F: Fn(&PinNode<T>) -> u64>(
+        &self,
+        node: &PinNode<T>,
+        weight: &F,
+        visited: &mut HashSet<PinNode<T>>,
+        weights: &mut HashMap<PinNode<T>, u64>,
+    ) -> u64 {
+        if !visited.insert(node.clone()) {
+            return *weights.get(node).unwrap_or(&0);
+        }
+        let mut total = weight(node);
+        if let Some(childs) = self.childs.get(node) {
+            for child in childs.iter() {
+                total += self.subtree_weight(child, weight, visited, weights);
+            }
+        }
+        weights.insert(node.clone(), total);
+        total
+    }
+    /// Like [`node`](Self::node), but surfaces allocation failure instead of aborting
+    pub fn try_node(&mut self, v: T) -> Result<PinNode<T>, TryReserveError> {
+        self.nodes.try_reserve(1)?;
+        let n: PinNode<T> = PinNode::new(v);
+        self.nodes.insert(n.clone());
+        Ok(n)
+    }
+    /// Like [`node_from`](Self::node_from), but surfaces allocation failure instead of aborting
+    pub fn try_node_from(&mut self, node: PinNode<T>) -> Result<bool, TryReserveError> {
+        self.nodes.try_reserve(1)?;
+        Ok(self.nodes.insert(node))
+    }
+    /// Like [`set_parent`](Self::set_parent), but surfaces allocation failure instead of aborting.
+    /// A single call may touch `nodes`, `parents` and `childs`, so each is reserved before insertion
+    pub fn try_set_parent(
+        &mut self,
+        this: &PinNode<T>,
+        parent: &PinNode<T>,
+    ) -> Result<bool, TryReserveError> {
+        self.nodes.try_reserve(2)?;
+        self.node_from(this.clone());
+        self.node_from(parent.clone());
+        if self.is_parent(this, parent) {
+            return Ok(false);
+        }
+
+        self.parents.try_reserve(1)?;
+        self.childs.try_reserve(1)?;
+        if let Some(childs) = self.childs.get_mut(parent) {
+            childs.try_reserve(1)?;
+        } else {
+            let mut childs = HashSet::new();
+            childs.try_reserve(1)?;
+            self.childs.insert(parent.clone(), childs);
+        }
+
+        if let Some(old_parent) = self.parents.get(this).cloned() {
+            self.remove_child(&old_parent, this);
+        }
+        self.remove_child(parent, this);
+        self.parents.insert(this.clone(), parent.clone());
+        self.childs.get_mut(parent).unwrap().insert(this.clone());
+        Ok(true)
+    }
+    /// Insert many `(child, parent)` edges at once, reserving capacity once up front instead of
+    /// rehashing repeatedly as happens when calling `set_parent` in a loop
+    pub fn set_parents<I: IntoIterator<Item = (PinNode<T>, PinNode<T>)>>(&mut self, edges: I) {
+        let edges = edges.into_iter();
+        let (lower, _) = edges.size_hint();
+        self.nodes.reserve(lower * 2);
+        self.parents.reserve(lower);
+        self.childs.reserve(lower);
+        for (child, parent) in edges {
+            self.set_parent(&child, &parent);
+        }
+    }
+    /// Validate that `nodes`, `parents` and `childs` are mutually consistent
+    pub fn verify_integrity(&self) -> Result<(), IntegrityError> {
+        for (child, parent) in self.parents.iter() {
+            if !self.nodes.contains(child) || !self.nodes.contains(parent) {
+                return Err(IntegrityError::DanglingParentEntry);
+            }
+        }
+        for (parent, childs) in self.childs.iter() {
+            if !self.nodes.contains(parent) {
+                return Err(IntegrityError::DanglingChildEntry);
+            }
+            for child in childs.iter() {
+                if !self.nodes.contains(child) {
+                    return Err(IntegrityError::DanglingChildEntry);
+                }
+            }
+        }
+        for (parent, childs) in self.childs.iter() {
+            for child in childs.iter() {
+                match self.parents.get(child) {
+                    Some(p) if p == parent => {}
+                    _ => return Err(IntegrityError::ParentChildMismatch),
+                }
+            }
+        }
+        for (child, parent) in self.parents.iter() {
+            let linked = self
+                .childs
+                .get(parent)
+                .map(|childs| childs.contains(child))
+                .unwrap_or(false);
+            if !linked {
+                return Err(IntegrityError::ChildParentMismatch);
+            }
+        }
+        Ok(())
+    }
+    /// Walk `parents` upward from `node` to the root, returning the path including `node` itself
+    /// Safe against cycles: stops as soon as a node would be visited twice
+    pub fn path_to_root(&self, node: &PinNode<T>) -> Vec<PinNode<T>> {
+        let mut visited = HashSet::new();
+        let mut path = Vec::new();
+        let mut current = Some(node.clone());
+        while let Some(n) = current {
+            if !visited.insert(n.clone()) {
+                break;
+            }
+            current = self.parents.get(&n).cloned();
+            path.push(n);
+        }
+        path
+    }
+    /// Find the lowest common ancestor of `a` and `b`, or `None` if their root paths never meet
+    pub fn lowest_common_ancestor(&self, a: &PinNode<T>, b: &PinNode<T>) -> Option<PinNode<T>> {
+        let ancestors_a: HashSet<PinNode<T>> = self.path_to_root(a).into_iter().collect();
+        self.path_to_root(b).into_iter().find(|n| ancestors_a.contains(n))
+    }
     /// Remove node from PinTree
     pub fn remove(&mut self, this: &PinNode<T>) -> bool {
         if !self.nodes.contains(this) {
@@ -174,8 +444,208 @@ impl<T> Display for PinTree<T> {
         write!(f, "PinTree{{...}}")
     }
 }
+impl<T: Hash> PinTree<T> {
+    /// Compute a Merkle-style structural hash of the subtree rooted at `node`
+    pub fn subtree_hash(&self, node: &PinNode<T>) -> [u8; 32] {
+        let mut visited = HashSet::new();
+        self.hash_walk(node, &mut visited)
+    }
+    /// Alias for [`subtree_hash`](Self::subtree_hash)
+    pub fn root_hash(&self, node: &PinNode<T>) -> [u8; 32] {
+        self.subtree_hash(node)
+    }
+    fn hash_walk(&self, node: &PinNode<T>, visited: &mut HashSet<PinNode<T>>) -> [u8; 32] {
+        if !visited.insert(node.clone()) {
+            return PRUNED_HASH;
+        }
+        let mut hasher = Sha256::new();
+        hasher.update(b"pintree.node");
+        hasher.update(value_bytes(node));
+
+        let childs = self.childs.get(node).cloned().unwrap_or_default();
+        if childs.is_empty() {
+            hasher.update(EMPTY_CHILDREN_HASH);
+        } else {
+            let mut child_hashes: Vec<[u8; 32]> =
+                childs.iter().map(|child| self.hash_walk(child, visited)).collect();
+            child_hashes.sort();
+            for h in child_hashes {
+                hasher.update(h);
+            }
+        }
+        hasher.finalize().into()
+    }
+}
+/// A `Hasher` that just records the raw bytes written to it, instead of folding them through an
+/// unspecified (and unkeyed-insecure) algorithm like `DefaultHasher`. Feeding those bytes to
+/// SHA-256 afterwards gives `subtree_hash` a stable, keyless, cryptographic digest of the value.
+#[derive(Default)]
+struct ByteCollector {
+    bytes: Vec<u8>,
+}
+impl Hasher for ByteCollector {
+    fn finish(&self) -> u64 {
+        0
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+}
+fn value_bytes<T: Hash>(node: &PinNode<T>) -> Vec<u8> {
+    let mut collector = ByteCollector::default();
+    (**node).hash(&mut collector);
+    collector.bytes
+}
+
+/// A nested, structural snapshot of a subtree produced by [`PinTree::serialize`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerializedNode {
+    /// The node's value, rendered via its `Display` impl
+    pub value: String,
+    /// Whether this entry is a back-reference to an already-visited node (a cycle), in which
+    /// case `value` holds the back-reference marker and `children` is empty
+    pub is_cycle: bool,
+    /// Recursively serialized children, in pointer-sorted order
+    pub children: Vec<SerializedNode>,
+}
+
+impl<T: Display> PinTree<T> {
+    /// Render `node` and its descendants as an indented ASCII tree with box-drawing connectors
+    pub fn to_tree_string(&self, node: &PinNode<T>) -> String {
+        use fmt::Write;
+
+        let mut out = String::new();
+        let mut visited = HashSet::new();
+        visited.insert(node.clone());
+        let _ = writeln!(out, "{}", node);
+        self.write_children(node, "", &mut visited, &mut out);
+        out
+    }
+    fn write_children(
+        &self,
+        node: &PinNode<T>,
+        prefix: &str,
+        visited: &mut HashSet<PinNode<T>>,
+        out: &mut String,
+    ) {
+        use fmt::Write;
+
+        let childs = self.sorted_childs(node);
+        let len = childs.len();
+        for (i, child) in childs.into_iter().enumerate() {
+            let is_last = i + 1 == len;
+            let connector = if is_last { "└─ " } else { "├─ " };
+            let child_prefix = format!("{}{}", prefix, if is_last { "   " } else { "│  " });
+            if visited.insert(child.clone()) {
+                let _ = writeln!(out, "{}{}{}", prefix, connector, child);
+                self.write_children(&child, &child_prefix, visited, out);
+            } else {
+                let _ = writeln!(out, "{}{}↺ {:#x}", prefix, connector, child.ptr_usize());
+            }
+        }
+    }
+    /// Serialize `node` and its descendants into a nested [`SerializedNode`] tree
+    pub fn serialize(&self, node: &PinNode<T>) -> SerializedNode {
+        let mut visited = HashSet::new();
+        self.serialize_walk(node, &mut visited)
+    }
+    fn serialize_walk(&self, node: &PinNode<T>, visited: &mut HashSet<PinNode<T>>) -> SerializedNode {
+        if !visited.insert(node.clone()) {
+            return SerializedNode {
+                value: format!("↺ {:#x}", node.ptr_usize()),
+                is_cycle: true,
+                children: Vec::new(),
+            };
+        }
+        let childs = self.sorted_childs(node);
+
+        SerializedNode {
+            value: format!("{}", node),
+            is_cycle: false,
+            children: childs
+                .iter()
+                .map(|c| self.serialize_walk(c, visited))
+                .collect(),
+        }
+    }
+}
+
+/// Builder for [`PinTree`] allowing upfront capacity reservation
+#[derive(Debug)]
+pub struct PinTreeBuilder<T> {
+    node_capacity: usize,
+    _marker: std::marker::PhantomData<T>,
+}
+impl<T> PinTreeBuilder<T> {
+    /// Create a PinTreeBuilder
+    pub fn new() -> Self {
+        Self {
+            node_capacity: 0,
+            _marker: std::marker::PhantomData,
+        }
+    }
+    /// Reserve capacity for `n` nodes in the underlying maps up front
+    pub fn with_node_capacity(mut self, n: usize) -> Self {
+        self.node_capacity = n;
+        self
+    }
+    /// Build the PinTree
+    pub fn build(self) -> PinTree<T> {
+        PinTree {
+            nodes: HashSet::with_capacity(self.node_capacity),
+            parents: HashMap::with_capacity(self.node_capacity),
+            childs: HashMap::with_capacity(self.node_capacity),
+            _empty_node_set: HashSet::new(),
+        }
+    }
+}
+impl<T> Default for PinTreeBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Describes the first structural inconsistency found by [`verify_integrity`](PinTree::verify_integrity)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityError {
+    /// A node referenced by `parents` is missing from `nodes`
+    DanglingParentEntry,
+    /// A node referenced by `childs` is missing from `nodes`
+    DanglingChildEntry,
+    /// `childs[parent]` contains `child`, but `parents[child] != parent`
+    ParentChildMismatch,
+    /// `parents[child] == parent`, but `childs[parent]` does not contain `child`
+    ChildParentMismatch,
+}
+impl Display for IntegrityError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let msg = match self {
+            IntegrityError::DanglingParentEntry => "a node in `parents` is missing from `nodes`",
+            IntegrityError::DanglingChildEntry => "a node in `childs` is missing from `nodes`",
+            IntegrityError::ParentChildMismatch => {
+                "`childs` and `parents` disagree about a parent-child pair"
+            }
+            IntegrityError::ChildParentMismatch => {
+                "`parents` and `childs` disagree about a parent-child pair"
+            }
+        };
+        write!(f, "{}", msg)
+    }
+}
+impl std::error::Error for IntegrityError {}
+
+/// Iterator over the `PinNode`s produced by a `PinTree` traversal
+pub struct NodeIter<T> {
+    inner: std::vec::IntoIter<PinNode<T>>,
+}
+impl<T> Iterator for NodeIter<T> {
+    type Item = PinNode<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
 
-/// PinNode is a PinArc Box  
+/// PinNode is a PinArc Box
 /// Wrap of Pin<Arc<T>>
 #[derive(Debug)]
 pub struct PinNode<T> {
@@ -186,6 +656,9 @@ impl<T> PinNode<T> {
     pub fn new(v: T) -> Self {
         PinNode { inner: Arc::pin(v) }
     }
+    pub(crate) fn ptr_usize(&self) -> usize {
+        self.inner.get_ptr_usize()
+    }
 }
 impl<T> Clone for PinNode<T> {
     fn clone(&self) -> Self {
@@ -335,4 +808,330 @@ mod tests {
         *x = 2;
         assert_eq!(*x, 2);
     }
+
+    #[test]
+    fn test_traversal() {
+        let mut pt = PinTree::<i32>::new();
+
+        let a = &pt.node(1);
+        let b = &pt.node(2);
+        let c = &pt.node(3);
+        let d = &pt.node(4);
+
+        pt.set_parent(b, a);
+        pt.set_parent(c, a);
+        pt.set_parent(d, b);
+        //      a
+        //    ↙  ↘
+        //   b     c
+        //  ↙
+        // d
+
+        let mut pre: Vec<_> = pt.descendants_pre_order(a).collect();
+        pre.sort_by_key(|n| **n);
+        assert_eq!(pre, vec![a.clone(), b.clone(), c.clone(), d.clone()]);
+
+        let mut post: Vec<_> = pt.descendants_post_order(a).collect();
+        post.sort_by_key(|n| **n);
+        assert_eq!(post, vec![a.clone(), b.clone(), c.clone(), d.clone()]);
+
+        let mut level: Vec<_> = pt.descendants_level_order(a).collect();
+        level.sort_by_key(|n| **n);
+        assert_eq!(level, vec![a.clone(), b.clone(), c.clone(), d.clone()]);
+
+        let ancestors: Vec<_> = pt.ancestors(d).collect();
+        assert_eq!(ancestors, vec![b.clone(), a.clone()]);
+    }
+
+    #[test]
+    fn test_traversal_circular_ref() {
+        let mut pt = PinTree::<i32>::new();
+
+        let a = &pt.node(1);
+        let b = &pt.node(2);
+        let c = &pt.node(3);
+
+        pt.set_parent(b, a);
+        pt.set_parent(a, c);
+        pt.set_parent(c, b);
+        //    a
+        //  ↙  ↖
+        // b  →  c
+
+        assert_eq!(pt.descendants_pre_order(a).count(), 3);
+        assert_eq!(pt.descendants_post_order(a).count(), 3);
+        assert_eq!(pt.descendants_level_order(a).count(), 3);
+        assert_eq!(pt.ancestors(a).count(), 2);
+    }
+
+    #[test]
+    fn test_find_head() {
+        let mut pt = PinTree::<i32>::new();
+
+        let a = &pt.node(1);
+        let b = &pt.node(2);
+        let c = &pt.node(3);
+        let d = &pt.node(4);
+        let e = &pt.node(5);
+
+        pt.set_parent(b, a);
+        pt.set_parent(c, a);
+        pt.set_parent(d, b);
+        pt.set_parent(e, c);
+        //      a
+        //    ↙  ↘
+        //   b     c
+        //  ↙       ↘
+        // d         e
+
+        // b's subtree weight (2) beats c's subtree weight (0), so the head is under b
+        let head = pt.find_head(a, |n| if **n == 2 || **n == 4 { 1 } else { 0 });
+        assert_eq!(*head, 4);
+
+        // a leaf with no children is its own head
+        let head = pt.find_head(d, |_| 1);
+        assert_eq!(*head, 4);
+    }
+
+    #[test]
+    fn test_lca() {
+        let mut pt = PinTree::<i32>::new();
+
+        let a = &pt.node(1);
+        let b = &pt.node(2);
+        let c = &pt.node(3);
+        let d = &pt.node(4);
+        let e = &pt.node(5);
+        let f = &pt.node(6);
+
+        pt.set_parent(b, a);
+        pt.set_parent(c, a);
+        pt.set_parent(d, b);
+        pt.set_parent(e, b);
+        pt.set_parent(f, c);
+        //      a
+        //    ↙  ↘
+        //   b     c
+        //  ↙ ↘     ↘
+        // d   e     f
+
+        assert_eq!(pt.path_to_root(d), vec![d.clone(), b.clone(), a.clone()]);
+        assert_eq!(pt.lowest_common_ancestor(d, e), Some(b.clone()));
+        assert_eq!(pt.lowest_common_ancestor(d, f), Some(a.clone()));
+
+        let unrelated = &pt.node(7);
+        assert_eq!(pt.lowest_common_ancestor(d, unrelated), None);
+    }
+
+    #[test]
+    fn test_try_variants() {
+        let mut pt = PinTree::<i32>::new();
+
+        let a = &pt.try_node(1).unwrap();
+        let b = &pt.try_node(2).unwrap();
+
+        assert_eq!(pt.try_set_parent(b, a).unwrap(), true);
+        assert_eq!(pt.is_parent(b, a), true);
+
+        let c = PinNode::new(3);
+        assert_eq!(pt.try_node_from(c.clone()).unwrap(), true);
+        assert_eq!(pt.has(&c), true);
+    }
+
+    #[test]
+    fn test_verify_integrity() {
+        let mut pt = PinTree::<i32>::new();
+
+        let a = &pt.node(1);
+        let b = &pt.node(2);
+
+        assert_eq!(pt.verify_integrity(), Ok(()));
+
+        pt.set_parent(b, a);
+        assert_eq!(pt.verify_integrity(), Ok(()));
+
+        pt.remove(a);
+        assert_eq!(pt.verify_integrity(), Ok(()));
+
+        pt.set_parent(b, a);
+        pt.nodes.remove(a);
+        assert_eq!(
+            pt.verify_integrity(),
+            Err(IntegrityError::DanglingParentEntry)
+        );
+    }
+
+    #[test]
+    fn test_verify_integrity_after_reparent() {
+        let mut pt = PinTree::<i32>::new();
+
+        let a = &pt.node(1);
+        let b = &pt.node(2);
+        let x = &pt.node(3);
+
+        pt.set_parent(b, a);
+        pt.set_parent(b, x);
+
+        // re-parenting must evict `b` from its old parent's childs set
+        assert_eq!(pt.is_child(a, b), false);
+        assert_eq!(pt.is_child(x, b), true);
+        assert_eq!(pt.verify_integrity(), Ok(()));
+    }
+
+    #[test]
+    fn test_builder_and_set_parents() {
+        let mut pt = PinTreeBuilder::<i32>::new().with_node_capacity(8).build();
+
+        let a = pt.node(1);
+        let b = pt.node(2);
+        let c = pt.node(3);
+
+        pt.set_parents(vec![(b.clone(), a.clone()), (c.clone(), a.clone())]);
+
+        assert_eq!(pt.is_parent(&b, &a), true);
+        assert_eq!(pt.is_parent(&c, &a), true);
+    }
+
+    #[test]
+    fn test_subtree_hash() {
+        let mut pt = PinTree::<i32>::new();
+
+        let a = &pt.node(1);
+        let b = &pt.node(2);
+        let c = &pt.node(3);
+
+        pt.set_parent(b, a);
+        pt.set_parent(c, a);
+
+        // hashing is deterministic and sibling order doesn't matter
+        assert_eq!(pt.subtree_hash(a), pt.subtree_hash(a));
+        assert_eq!(pt.root_hash(a), pt.subtree_hash(a));
+
+        // a different value changes the hash
+        let mut pt2 = PinTree::<i32>::new();
+        let a2 = &pt2.node(1);
+        let b2 = &pt2.node(2);
+        let c2 = &pt2.node(4);
+        pt2.set_parent(b2, a2);
+        pt2.set_parent(c2, a2);
+        assert_ne!(pt.subtree_hash(a), pt2.subtree_hash(a2));
+
+        // a leaf hashes the same regardless of which tree it lives in
+        assert_eq!(pt.subtree_hash(b), pt2.subtree_hash(b2));
+    }
+
+    #[test]
+    fn test_subtree_hash_allocation_order_independent() {
+        // same structure and values, but children are allocated (and so pointer-ordered)
+        // differently between the two trees
+        let mut pt = PinTree::<i32>::new();
+        let a = &pt.node(1);
+        let b = &pt.node(2);
+        let c = &pt.node(3);
+        pt.set_parent(b, a);
+        pt.set_parent(c, a);
+
+        let mut pt2 = PinTree::<i32>::new();
+        let a2 = &pt2.node(1);
+        let c2 = &pt2.node(3);
+        let b2 = &pt2.node(2);
+        pt2.set_parent(c2, a2);
+        pt2.set_parent(b2, a2);
+
+        assert_eq!(pt.subtree_hash(a), pt2.subtree_hash(a2));
+    }
+
+    #[test]
+    fn test_subtree_hash_circular_ref() {
+        let mut pt = PinTree::<i32>::new();
+
+        let a = &pt.node(1);
+        let b = &pt.node(2);
+        let c = &pt.node(3);
+
+        pt.set_parent(b, a);
+        pt.set_parent(a, c);
+        pt.set_parent(c, b);
+
+        // must terminate and be stable across repeated calls despite the cycle
+        assert_eq!(pt.subtree_hash(a), pt.subtree_hash(a));
+    }
+
+    #[test]
+    fn test_to_tree_string() {
+        let mut pt = PinTree::<i32>::new();
+
+        let a = &pt.node(1);
+        let b = &pt.node(2);
+        let c = &pt.node(3);
+
+        pt.set_parent(b, a);
+        pt.set_parent(c, a);
+
+        let s = pt.to_tree_string(a);
+        assert!(s.starts_with("PinNode(1)\n"));
+        assert!(s.contains("PinNode(2)"));
+        assert!(s.contains("PinNode(3)"));
+        assert!(s.contains("├─ ") || s.contains("└─ "));
+    }
+
+    #[test]
+    fn test_to_tree_string_circular_ref() {
+        let mut pt = PinTree::<i32>::new();
+
+        let a = &pt.node(1);
+        let b = &pt.node(2);
+        let c = &pt.node(3);
+
+        pt.set_parent(b, a);
+        pt.set_parent(a, c);
+        pt.set_parent(c, b);
+
+        // must terminate and mark the back-edge instead of recursing forever
+        let s = pt.to_tree_string(a);
+        assert!(s.contains('↺'));
+    }
+
+    #[test]
+    fn test_serialize() {
+        let mut pt = PinTree::<i32>::new();
+
+        let a = &pt.node(1);
+        let b = &pt.node(2);
+
+        pt.set_parent(b, a);
+
+        let s = pt.serialize(a);
+        assert_eq!(s.value, "PinNode(1)");
+        assert_eq!(s.is_cycle, false);
+        assert_eq!(s.children.len(), 1);
+        assert_eq!(s.children[0].value, "PinNode(2)");
+        assert_eq!(s.children[0].children.len(), 0);
+    }
+
+    #[test]
+    fn test_serialize_circular_ref() {
+        let mut pt = PinTree::<i32>::new();
+
+        let a = &pt.node(1);
+        let b = &pt.node(2);
+        let c = &pt.node(3);
+
+        pt.set_parent(b, a);
+        pt.set_parent(a, c);
+        pt.set_parent(c, b);
+
+        let s = pt.serialize(a);
+        assert_eq!(s.is_cycle, false);
+        assert_eq!(s.children.len(), 1);
+        let child = &s.children[0];
+        assert_eq!(child.is_cycle, false);
+        assert_eq!(child.children.len(), 1);
+        let grandchild = &child.children[0];
+        assert_eq!(grandchild.is_cycle, false);
+        assert_eq!(grandchild.children.len(), 1);
+        let back_ref = &grandchild.children[0];
+        assert_eq!(back_ref.is_cycle, true);
+        assert_eq!(back_ref.children.len(), 0);
+    }
 }